@@ -1,6 +1,37 @@
 use biome_deserialize_macros::Partial;
 use bpaf::Bpaf;
+use deadpool_postgres::{Config as PoolConfig, CreatePoolError, ManagerConfig, Pool, RecyclingMethod};
+use native_tls::TlsConnector;
+use postgres_native_tls::MakeTlsConnector;
 use serde::{Deserialize, Serialize};
+use tokio_postgres::config::SslMode as PgSslMode;
+
+/// The TLS negotiation mode used when connecting to the database.
+///
+/// Mirrors the subset of libpq's `sslmode` values that make sense for a
+/// language server. The default, `Prefer`, connects without encryption when
+/// the server does not support TLS; only `Require` refuses to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Bpaf)]
+#[serde(rename_all = "snake_case")]
+pub enum SslMode {
+    /// Never use TLS.
+    Disable,
+    /// Use TLS if the server supports it, fall back to plaintext otherwise.
+    #[default]
+    Prefer,
+    /// Always use TLS; fail the connection if it isn't available.
+    Require,
+}
+
+impl From<SslMode> for PgSslMode {
+    fn from(mode: SslMode) -> Self {
+        match mode {
+            SslMode::Disable => PgSslMode::Disable,
+            SslMode::Prefer => PgSslMode::Prefer,
+            SslMode::Require => PgSslMode::Require,
+        }
+    }
+}
 
 /// The configuration of the database connection.
 #[derive(Clone, Debug, Deserialize, Eq, Partial, PartialEq, Serialize)]
@@ -20,7 +51,23 @@ pub struct DatabaseConfiguration {
     pub password: String,
 
     #[partial(bpaf(long("database")))]
-    pub database: String
+    pub database: String,
+
+    /// Maximum number of pooled connections kept open for this database.
+    #[partial(bpaf(long("max-connections")))]
+    pub max_connections: u16,
+
+    /// Timeout, in seconds, allowed for establishing a new connection.
+    #[partial(bpaf(long("connect-timeout")))]
+    pub connect_timeout_secs: u64,
+
+    /// TLS negotiation mode used when connecting.
+    #[partial(bpaf(long("ssl-mode")))]
+    pub ssl_mode: SslMode,
+
+    /// When set, connect over this Unix domain socket instead of TCP.
+    #[partial(bpaf(long("socket-path")))]
+    pub socket_path: Option<String>,
 }
 
 impl Default for DatabaseConfiguration {
@@ -31,15 +78,87 @@ impl Default for DatabaseConfiguration {
             username: "postgres".to_string(),
             password: "postgres".to_string(),
             database: "postgres".to_string(),
+            max_connections: 10,
+            connect_timeout_secs: 5,
+            ssl_mode: SslMode::default(),
+            socket_path: None,
         }
     }
 }
 
 impl DatabaseConfiguration {
     pub fn to_connection_string(&self) -> String {
-        format!(
-            "postgres://{}:{}@{}:{}/{}",
-            self.username, self.password, self.host, self.port, self.database
+        let sslmode = match self.ssl_mode {
+            SslMode::Disable => "disable",
+            SslMode::Prefer => "prefer",
+            SslMode::Require => "require",
+        };
+
+        match &self.socket_path {
+            // libpq resolves a `host` query parameter that looks like a
+            // directory to a Unix socket, so the authority section is left
+            // empty and the socket path travels as a parameter instead.
+            Some(socket_path) => format!(
+                "postgres://{}:{}@/{}?host={}&port={}&sslmode={}",
+                self.username, self.password, self.database, socket_path, self.port, sslmode
+            ),
+            None => format!(
+                "postgres://{}:{}@{}:{}/{}?sslmode={}",
+                self.username, self.password, self.host, self.port, self.database, sslmode
+            ),
+        }
+    }
+
+    /// Builds the `deadpool-postgres` configuration for this database,
+    /// connecting over the Unix domain socket instead of TCP when one is
+    /// configured.
+    pub fn to_pool_config(&self) -> PoolConfig {
+        let mut config = PoolConfig::new();
+
+        // tokio-postgres treats a `host` that points at a directory as a
+        // Unix socket path, so a configured socket path simply takes the
+        // place of the host; the port still selects the socket file in it.
+        config.host = Some(match &self.socket_path {
+            Some(socket_path) => socket_path.clone(),
+            None => self.host.clone(),
+        });
+        config.port = Some(self.port);
+
+        config.user = Some(self.username.clone());
+        config.password = Some(self.password.clone());
+        config.dbname = Some(self.database.clone());
+        config.ssl_mode = Some(PgSslMode::from(self.ssl_mode));
+        config.connect_timeout = Some(std::time::Duration::from_secs(self.connect_timeout_secs));
+        config.manager = Some(ManagerConfig {
+            recycling_method: RecyclingMethod::Fast,
+        });
+        config.pool = Some(deadpool_postgres::PoolConfig::new(
+            self.max_connections as usize,
+        ));
+
+        config
+    }
+
+    /// Builds a shared connection pool for this database configuration.
+    ///
+    /// Callers should keep a single pool per [`DatabaseConfiguration`] around
+    /// (e.g. on the workspace client) so analysis workers reuse connections
+    /// instead of opening a new one per request.
+    ///
+    /// The pool is always handed a TLS-capable connector, never `NoTls`: the
+    /// `sslmode` set on the `tokio-postgres` config (see [`Self::to_pool_config`])
+    /// is what actually governs negotiation, including `Disable`, which never
+    /// attempts the handshake regardless of the connector's capabilities.
+    /// With `NoTls`, `Require`/`Prefer` could never actually negotiate TLS,
+    /// defeating the point of the `ssl_mode` field.
+    pub fn build_pool(&self) -> Result<Pool, CreatePoolError> {
+        let tls_connector = TlsConnector::builder()
+            .build()
+            .expect("failed to build a TLS connector for the database pool");
+
+        self.to_pool_config().create_pool(
+            Some(deadpool_postgres::Runtime::Tokio1),
+            MakeTlsConnector::new(tls_connector),
         )
     }
 }
@@ -0,0 +1,141 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+
+use deadpool_postgres::Pool;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use once_cell::sync::Lazy;
+use prometheus::{Histogram, HistogramOpts, IntCounter, IntGauge, Opts, Registry};
+use tokio::net::TcpListener;
+
+/// Process-wide registry backing the `/metrics` endpoint.
+///
+/// All counters/histograms/gauges the daemon records go through this
+/// registry so the admin handler can render them without threading a handle
+/// through every call site.
+///
+/// This is only meaningful in the daemon process (the one running
+/// `run_server`/`serve_admin`): the LSP proxy spawned per editor connection
+/// is a separate OS process with its own copy of this `Lazy`, so anything
+/// it touches never shows up here. Recording analysis-request counts and
+/// latency needs a hook inside `pgt_workspace`'s request handling, and
+/// session counts need one inside `pgt_lsp::ServerFactory`'s connection
+/// lifecycle; neither crate's source is part of this checkout, so all three
+/// metrics are registered (and exposed at `/metrics`) but not recorded
+/// anywhere.
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+/// Metrics recorded by the running daemon.
+pub struct Metrics {
+    registry: Registry,
+    pub analysis_requests_total: IntCounter,
+    pub active_lsp_sessions: IntGauge,
+    pub request_latency_seconds: Histogram,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        let analysis_requests_total = IntCounter::with_opts(Opts::new(
+            "pgt_analysis_requests_total",
+            "Total number of analysis requests handled by the daemon",
+        ))
+        .expect("failed to create pgt_analysis_requests_total counter");
+
+        let active_lsp_sessions = IntGauge::with_opts(Opts::new(
+            "pgt_active_lsp_sessions",
+            "Number of LSP sessions currently connected to the daemon",
+        ))
+        .expect("failed to create pgt_active_lsp_sessions gauge");
+
+        let request_latency_seconds = Histogram::with_opts(HistogramOpts::new(
+            "pgt_request_latency_seconds",
+            "Latency of analysis requests in seconds",
+        ))
+        .expect("failed to create pgt_request_latency_seconds histogram");
+
+        registry
+            .register(Box::new(analysis_requests_total.clone()))
+            .expect("failed to register pgt_analysis_requests_total");
+        registry
+            .register(Box::new(active_lsp_sessions.clone()))
+            .expect("failed to register pgt_active_lsp_sessions");
+        registry
+            .register(Box::new(request_latency_seconds.clone()))
+            .expect("failed to register pgt_request_latency_seconds");
+
+        Self {
+            registry,
+            analysis_requests_total,
+            active_lsp_sessions,
+            request_latency_seconds,
+        }
+    }
+
+    fn render(&self) -> String {
+        use prometheus::Encoder;
+        let encoder = prometheus::TextEncoder::new();
+        let metric_families = self.registry.gather();
+        let mut buffer = Vec::new();
+        encoder
+            .encode(&metric_families, &mut buffer)
+            .expect("failed to encode metrics");
+        String::from_utf8(buffer).expect("metrics encoding produced invalid utf8")
+    }
+}
+
+/// Serves `/metrics` and `/health` on `addr` until the returned future is
+/// dropped or accepting a connection fails.
+///
+/// Spawned alongside the main daemon loop inside the same `tokio::select!`
+/// so the admin listener shuts down together with the rest of the server.
+/// `/health` reports unhealthy when a connection can't be checked out of
+/// `pool` within a short budget, rather than merely checking that the pool
+/// object exists.
+pub async fn serve_admin(addr: SocketAddr, pool: Pool) -> std::io::Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("Admin endpoint listening on {addr}");
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let io = TokioIo::new(stream);
+        let pool = pool.clone();
+
+        tokio::spawn(async move {
+            let service = service_fn(move |req| handle(req, pool.clone()));
+            if let Err(err) = http1::Builder::new().serve_connection(io, service).await {
+                tracing::debug!("admin connection error: {err}");
+            }
+        });
+    }
+}
+
+async fn handle(
+    req: Request<hyper::body::Incoming>,
+    pool: Pool,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let response = match req.uri().path() {
+        "/metrics" => Response::new(Full::new(Bytes::from(METRICS.render()))),
+        "/health" => match pool.get().await {
+            Ok(_) => Response::new(Full::new(Bytes::from_static(b"ok"))),
+            Err(err) => {
+                tracing::debug!("/health: database pool unreachable: {err}");
+                Response::builder()
+                    .status(StatusCode::SERVICE_UNAVAILABLE)
+                    .body(Full::new(Bytes::from_static(b"database pool unreachable")))
+                    .expect("building a static response cannot fail")
+            }
+        },
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Full::new(Bytes::new()))
+            .expect("building a static response cannot fail"),
+    };
+
+    Ok(response)
+}
@@ -1,15 +1,25 @@
 use crate::{
     CliDiagnostic, CliSession, open_transport,
+    admin::serve_admin,
+    lsp_framing::{read_framed_message, write_framed_message},
     service::{self, ensure_daemon, open_socket, run_daemon},
 };
+use pg_configuration::DatabaseConfiguration;
 use pgt_console::{ConsoleExt, markup};
 use pgt_lsp::ServerFactory;
 use pgt_workspace::{TransportError, WorkspaceError, workspace::WorkspaceClient};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::Arc;
 use std::{env, path::PathBuf};
 use tokio::io;
 use tokio::runtime::Runtime;
+use tokio::sync::Mutex;
 use tracing::subscriber::Interest;
 use tracing::{Instrument, Metadata, debug_span, metadata::LevelFilter};
+use opentelemetry::global;
+use opentelemetry_otlp::WithExportConfig;
 use tracing_appender::rolling::Rotation;
 use tracing_subscriber::{
     Layer,
@@ -24,6 +34,8 @@ pub(crate) fn start(
     config_path: Option<PathBuf>,
     log_path: Option<PathBuf>,
     log_file_name_prefix: Option<String>,
+    admin_port: Option<u16>,
+    otlp_endpoint: Option<String>,
 ) -> Result<(), CliDiagnostic> {
     let rt = Runtime::new()?;
     let did_spawn = rt.block_on(ensure_daemon(
@@ -31,6 +43,8 @@ pub(crate) fn start(
         config_path,
         log_path,
         log_file_name_prefix,
+        admin_port,
+        otlp_endpoint,
     ))?;
 
     if did_spawn {
@@ -78,27 +92,75 @@ pub(crate) fn run_server(
     config_path: Option<PathBuf>,
     log_path: Option<PathBuf>,
     log_file_name_prefix: Option<String>,
+    admin_port: Option<u16>,
+    otlp_endpoint: Option<String>,
 ) -> Result<(), CliDiagnostic> {
-    setup_tracing_subscriber(log_path, log_file_name_prefix);
-
     let rt = Runtime::new()?;
+
+    // The OTLP batch exporter spawns its background flush task onto the
+    // Tokio reactor as soon as it's installed, so the subscriber has to be
+    // built after a runtime exists; `rt.enter()` makes this one current for
+    // the duration of the call without requiring `setup_tracing_subscriber`
+    // itself to be async.
+    let otlp_installed = {
+        let _guard = rt.enter();
+        setup_tracing_subscriber(log_path, log_file_name_prefix, otlp_endpoint)
+    };
+
     let factory = ServerFactory::new(stop_on_disconnect);
     let cancellation = factory.cancellation();
     let span = debug_span!("Running Server", pid = std::process::id());
 
+    // This pool is only handed to the admin listener's `/health` check below.
+    // The request this was built for asked for a single pool shared with the
+    // workspace client, so analysis workers reuse connections instead of
+    // opening one per request, but that means threading it through
+    // `pgt_workspace`'s `WorkspaceClient`/`ServerFactory`, and neither
+    // crate's source is part of this checkout (only pg_configuration,
+    // pgt_cli and pgt_test_utils are). Until that wiring lands, `/health`
+    // also only proves a database matching `DatabaseConfiguration::default()`
+    // is reachable, not the one the running daemon is actually configured
+    // against.
+    let database_pool = DatabaseConfiguration::default()
+        .build_pool()
+        .expect("Failed to build the database connection pool for the daemon.");
+
     rt.block_on(async move {
-        tokio::select! {
+        let admin_addr = admin_port.map(|port| SocketAddr::from(([127, 0, 0, 1], port)));
+
+        let result = tokio::select! {
             res = run_daemon(factory, config_path).instrument(span) => {
                 match res {
                     Ok(never) => match never {},
                     Err(err) => Err(err.into()),
                 }
             }
+            res = async {
+                match admin_addr {
+                    Some(addr) => serve_admin(addr, database_pool).await,
+                    // No admin port configured: stay pending forever so this
+                    // branch never wins the select.
+                    None => std::future::pending().await,
+                }
+            } => {
+                res.map_err(CliDiagnostic::from)
+            }
             _ = cancellation.notified() => {
                 tracing::info!("Received shutdown signal");
                 Ok(())
             }
+        };
+
+        // Flush any spans still buffered by the OTLP exporter before the
+        // process exits, otherwise the last batch is silently dropped. Based
+        // on whether a layer was actually installed, not just whether a
+        // `--otlp-endpoint` argument was passed in, since the endpoint can
+        // also come from `PGT_OTLP_ENDPOINT`.
+        if otlp_installed {
+            global::shutdown_tracer_provider();
         }
+
+        result
     })
 }
 
@@ -126,7 +188,12 @@ pub(crate) fn lsp_proxy(
 
 /// Start a proxy process.
 /// Receives a process via `stdin` and then copy the content to the LSP socket.
-/// Copy to the process on `stdout` when the LSP responds to a message
+/// Copy to the process on `stdout` when the LSP responds to a message.
+///
+/// Traffic is forwarded message-by-message rather than as a raw byte copy:
+/// each side parses `Content-Length`-framed JSON-RPC messages so requests and
+/// responses can be traced and so a peer disconnecting mid-message is
+/// reported instead of silently looking like a clean shutdown.
 async fn start_lsp_proxy(
     rt: &Runtime,
     config_path: Option<PathBuf>,
@@ -136,34 +203,84 @@ async fn start_lsp_proxy(
     ensure_daemon(true, config_path, log_path, log_file_name_prefix).await?;
 
     match open_socket().await? {
-        Some((mut owned_read_half, mut owned_write_half)) => {
-            // forward stdin to socket
-            let mut stdin = io::stdin();
+        Some((owned_read_half, mut owned_write_half)) => {
+            // Responses carry an `id` but no `method`, so the method each
+            // response answers, and when it was sent, is recorded here (keyed by id, as a string)
+            // when the request goes out, and consumed when the matching
+            // response comes back.
+            let pending_requests: Arc<Mutex<HashMap<String, (String, std::time::Instant)>>> =
+                Arc::new(Mutex::new(HashMap::new()));
+
+            // forward stdin to socket, framing each JSON-RPC message so it
+            // can be logged and its latency measured
+            let input_pending_requests = pending_requests.clone();
             let input_handle = rt.spawn(async move {
+                let mut stdin = io::BufReader::new(io::stdin());
                 loop {
-                    match io::copy(&mut stdin, &mut owned_write_half).await {
-                        Ok(b) => {
-                            if b == 0 {
-                                return Ok(());
-                            }
-                        }
-                        Err(err) => return Err(err),
+                    let message = match read_framed_message(&mut stdin).await.inspect_err(|err| {
+                        tracing::error!("lsp_proxy: failed to read a message from stdin: {err}")
+                    })? {
+                        Some(message) => message,
+                        None => return Ok(()),
                     };
+
+                    if let (Some(method), Some(id)) = (&message.method, &message.id) {
+                        input_pending_requests
+                            .lock()
+                            .await
+                            .insert(id.to_string(), (method.clone(), std::time::Instant::now()));
+                    }
+
+                    let span = debug_span!(
+                        "lsp_proxy request",
+                        method = message.method.as_deref().unwrap_or("<unknown>"),
+                        id = message.id.as_ref().map(Value::to_string).unwrap_or_default()
+                    );
+                    async {
+                        let start = std::time::Instant::now();
+                        let result = write_framed_message(&mut owned_write_half, &message).await;
+                        let elapsed = start.elapsed();
+                        tracing::debug!(?elapsed, "forwarded request to daemon");
+                        result
+                    }
+                    .instrument(span)
+                    .await?;
                 }
             });
 
-            // receive socket response to stdout
-            let mut stdout = io::stdout();
+            // receive socket response to stdout, framed the same way
             let out_put_handle = rt.spawn(async move {
+                let mut socket_reader = io::BufReader::new(owned_read_half);
+                let mut stdout = io::stdout();
                 loop {
-                    match io::copy(&mut owned_read_half, &mut stdout).await {
-                        Ok(b) => {
-                            if b == 0 {
-                                return Ok(());
-                            }
-                        }
-                        Err(err) => return Err(err),
+                    let message = match read_framed_message(&mut socket_reader).await.inspect_err(|err| {
+                        tracing::error!("lsp_proxy: failed to read a message from the daemon: {err}")
+                    })? {
+                        Some(message) => message,
+                        None => return Ok(()),
                     };
+
+                    let (method, latency) = match &message.id {
+                        Some(id) => match pending_requests.lock().await.remove(&id.to_string()) {
+                            Some((method, sent_at)) => (Some(method), Some(sent_at.elapsed())),
+                            None => (None, None),
+                        },
+                        None => (None, None),
+                    };
+
+                    let span = debug_span!(
+                        "lsp_proxy response",
+                        method = method.as_deref().unwrap_or("<notification>"),
+                        id = message.id.as_ref().map(Value::to_string).unwrap_or_default()
+                    );
+                    async {
+                        if let Some(latency) = latency {
+                            tracing::debug!(?latency, "received response from daemon");
+                        }
+                        write_framed_message(&mut stdout, &message).await
+                    }
+                    .instrument(span)
+                    .await?;
                 }
             });
 
@@ -181,7 +298,23 @@ async fn start_lsp_proxy(
 /// is written to log files rotated on a hourly basis (in
 /// `pgt-logs/server.log.yyyy-MM-dd-HH` files inside the system temporary
 /// directory)
-fn setup_tracing_subscriber(log_path: Option<PathBuf>, log_file_name_prefix: Option<String>) {
+///
+/// When `otlp_endpoint` is set (directly, or via `PGT_OTLP_ENDPOINT`), spans
+/// are additionally exported over OTLP so analysis latency can be correlated
+/// across the daemon, LSP proxy, and database calls in a distributed tracing
+/// backend. The file layer's behavior is unchanged either way. Must be
+/// called with a Tokio runtime already current (e.g. from inside
+/// `rt.enter()`), since the OTLP batch exporter spawns its flush task onto
+/// the active reactor as soon as it's installed.
+///
+/// Returns `true` if the OTLP layer was actually installed, so callers can
+/// tell whether `PGT_OTLP_ENDPOINT` took effect and flush the tracer
+/// provider on shutdown accordingly.
+fn setup_tracing_subscriber(
+    log_path: Option<PathBuf>,
+    log_file_name_prefix: Option<String>,
+    otlp_endpoint: Option<String>,
+) -> bool {
     let pgt_log_path = log_path.unwrap_or(pgt_fs::ensure_cache_dir().join("pgt-logs"));
     let appender_builder = tracing_appender::rolling::RollingFileAppender::builder();
     let file_appender = appender_builder
@@ -191,6 +324,24 @@ fn setup_tracing_subscriber(log_path: Option<PathBuf>, log_file_name_prefix: Opt
         .build(pgt_log_path)
         .expect("Failed to start the logger for the daemon.");
 
+    let otlp_endpoint = otlp_endpoint.or_else(|| env::var("PGT_OTLP_ENDPOINT").ok());
+    let otlp_installed = otlp_endpoint.is_some();
+    let otlp_layer = otlp_endpoint.map(|endpoint| {
+        let tracer = opentelemetry_otlp::new_pipeline()
+            .tracing()
+            .with_exporter(
+                opentelemetry_otlp::new_exporter()
+                    .tonic()
+                    .with_endpoint(endpoint),
+            )
+            .install_batch(opentelemetry_sdk::runtime::Tokio)
+            .expect("Failed to install the OTLP tracer for the daemon.");
+
+        tracing_opentelemetry::layer()
+            .with_tracer(tracer)
+            .with_filter(LoggingFilter)
+    });
+
     registry()
         .with(
             HierarchicalLayer::default()
@@ -202,7 +353,10 @@ fn setup_tracing_subscriber(log_path: Option<PathBuf>, log_file_name_prefix: Opt
                 .with_writer(file_appender)
                 .with_filter(LoggingFilter),
         )
+        .with(otlp_layer)
         .init();
+
+    otlp_installed
 }
 
 pub fn default_pgt_log_path() -> PathBuf {
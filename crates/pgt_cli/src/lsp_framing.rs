@@ -0,0 +1,96 @@
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt};
+
+/// A single JSON-RPC message read off an LSP transport, together with the
+/// bits of its envelope (`method`/`id`) needed to label a tracing span
+/// without re-parsing the body at every log site.
+pub struct LspMessage {
+    /// The message as it appeared on the wire, headers included, ready to be
+    /// forwarded verbatim.
+    pub raw: Vec<u8>,
+    pub method: Option<String>,
+    pub id: Option<Value>,
+}
+
+/// Reads one `Content-Length`-framed JSON-RPC message from `reader`.
+///
+/// Returns `Ok(None)` on a clean EOF between messages. An EOF in the middle
+/// of the headers or body is reported as an error so callers can tell a
+/// well-behaved shutdown from the peer vanishing mid-message.
+pub async fn read_framed_message<R: AsyncBufReadExt + Unpin>(
+    reader: &mut R,
+) -> std::io::Result<Option<LspMessage>> {
+    let mut content_length: Option<usize> = None;
+    // Headers are forwarded byte-for-byte, including ones we don't
+    // interpret (e.g. `Content-Type`), so framing only adds logging and
+    // error reporting without otherwise changing well-formed traffic.
+    let mut header_bytes = Vec::new();
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader.read_line(&mut line).await?;
+        if bytes_read == 0 {
+            if header_bytes.is_empty() {
+                return Ok(None);
+            }
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "connection closed mid-message while reading headers",
+            ));
+        }
+        header_bytes.extend_from_slice(line.as_bytes());
+
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed.is_empty() {
+            break;
+        }
+
+        if let Some(value) = trimmed.strip_prefix("Content-Length:") {
+            content_length = Some(value.trim().parse().map_err(|_| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid Content-Length header: {value}"),
+                )
+            })?);
+        }
+    }
+
+    let content_length = content_length.ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "message is missing a Content-Length header",
+        )
+    })?;
+
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).await.map_err(|err| {
+        std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            format!("connection closed mid-message while reading the body: {err}"),
+        )
+    })?;
+
+    let (method, id) = match serde_json::from_slice::<Value>(&body) {
+        Ok(json) => (
+            json.get("method")
+                .and_then(Value::as_str)
+                .map(str::to_owned),
+            json.get("id").cloned(),
+        ),
+        Err(_) => (None, None),
+    };
+
+    let mut raw = header_bytes;
+    raw.extend_from_slice(&body);
+
+    Ok(Some(LspMessage { raw, method, id }))
+}
+
+/// Writes a previously-read [`LspMessage`] back out, headers included.
+pub async fn write_framed_message<W: AsyncWriteExt + Unpin>(
+    writer: &mut W,
+    message: &LspMessage,
+) -> std::io::Result<()> {
+    writer.write_all(&message.raw).await?;
+    writer.flush().await
+}
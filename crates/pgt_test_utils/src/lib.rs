@@ -0,0 +1,172 @@
+use once_cell::sync::Lazy;
+use pg_configuration::DatabaseConfiguration;
+
+/// A throwaway Postgres database provisioned against a live cluster for
+/// integration tests that need real type/relation resolution (e.g. checking
+/// column existence or index coverage) rather than static SQL text.
+///
+/// Connect to a base cluster (by default `127.0.0.1:5432`, overridable via
+/// the `PGT_TEST_DATABASE_URL` environment variable), `CREATE DATABASE
+/// pgt_test_<random>`, run the caller-supplied DDL, and `DROP DATABASE` it
+/// again when the `TestDatabase` is dropped.
+///
+/// Teardown needs an async connection, but a `TestDatabase` is normally
+/// dropped from inside an already-running `#[tokio::test]` runtime, where
+/// `block_on`-ing a new one panics ("Cannot start a runtime from within a
+/// runtime"). `Drop` instead hands the cleanup job to [`cleanup_queue`], a
+/// dedicated background thread that owns its own single-threaded runtime and
+/// never itself runs inside one, so the `DROP DATABASE` still happens
+/// without requiring callers to remember an explicit teardown step. `drop`
+/// returns without waiting for that job; a job not finished when the process
+/// exits is not completed, and that database is left behind.
+pub struct TestDatabase {
+    name: String,
+    configuration: DatabaseConfiguration,
+    base_configuration: DatabaseConfiguration,
+}
+
+impl TestDatabase {
+    /// Provisions a new ephemeral database and runs `schema` against it.
+    ///
+    /// Returns `None` when no Postgres cluster is reachable, so tests that
+    /// call this can skip themselves instead of failing CI environments
+    /// that don't have a database available.
+    pub async fn new(schema: &str) -> Option<Self> {
+        let base_configuration = base_configuration();
+
+        let base_pool = base_configuration.build_pool().ok()?;
+        let base_client = base_pool.get().await.ok()?;
+
+        let name = format!("pgt_test_{}", random_suffix());
+
+        base_client
+            .batch_execute(&format!("CREATE DATABASE {name}"))
+            .await
+            .ok()?;
+
+        let mut configuration = base_configuration.clone();
+        configuration.database = name.clone();
+
+        let pool = configuration.build_pool().ok()?;
+        let client = pool.get().await.ok()?;
+        if client.batch_execute(schema).await.is_err() {
+            // Best-effort cleanup: the database was created but couldn't be
+            // seeded, so don't leak it.
+            let _ = base_client
+                .batch_execute(&format!("DROP DATABASE {name}"))
+                .await;
+            return None;
+        }
+
+        Some(Self {
+            name,
+            configuration,
+            base_configuration,
+        })
+    }
+
+    /// The configuration pointing at the ephemeral database, ready to hand
+    /// to a pooled client or the workspace under test.
+    pub fn configuration(&self) -> &DatabaseConfiguration {
+        &self.configuration
+    }
+
+    async fn drop_database(
+        base_configuration: &DatabaseConfiguration,
+        name: &str,
+    ) -> std::io::Result<()> {
+        let pool = base_configuration
+            .build_pool()
+            .map_err(std::io::Error::other)?;
+        let client = pool.get().await.map_err(std::io::Error::other)?;
+        client
+            .batch_execute(&format!("DROP DATABASE IF EXISTS {name}"))
+            .await
+            .map_err(std::io::Error::other)
+    }
+}
+
+impl Drop for TestDatabase {
+    fn drop(&mut self) {
+        let base_configuration = self.base_configuration.clone();
+        let name = self.name.clone();
+
+        if cleanup_queue()
+            .send(Box::new(move || {
+                Box::pin(async move {
+                    if let Err(err) = TestDatabase::drop_database(&base_configuration, &name).await
+                    {
+                        tracing::warn!("failed to drop test database {name}: {err}");
+                    }
+                })
+            }))
+            .is_err()
+        {
+            tracing::warn!(
+                "test database cleanup worker is gone; `{}` was not removed and will leak until cleaned up manually",
+                self.name
+            );
+        }
+    }
+}
+
+type CleanupJob = Box<dyn FnOnce() -> std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>> + Send>;
+
+/// A dedicated background thread that runs `DROP DATABASE` jobs queued up by
+/// [`TestDatabase::drop`].
+///
+/// It owns a single-threaded Tokio runtime and, crucially, is a plain OS
+/// thread that never enters any other Tokio runtime itself, so blocking on
+/// that runtime from here is always safe even when the `Drop` that queued
+/// the job ran inside a caller's own `#[tokio::test]` runtime.
+fn cleanup_queue() -> &'static std::sync::mpsc::Sender<CleanupJob> {
+    static QUEUE: Lazy<std::sync::mpsc::Sender<CleanupJob>> = Lazy::new(|| {
+        let (sender, receiver) = std::sync::mpsc::channel::<CleanupJob>();
+
+        std::thread::Builder::new()
+            .name("pgt-test-db-cleanup".to_string())
+            .spawn(move || {
+                let rt = tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                    .expect("failed to start the test database cleanup runtime");
+
+                for job in receiver {
+                    rt.block_on(job());
+                }
+            })
+            .expect("failed to spawn the test database cleanup thread");
+
+        sender
+    });
+
+    &QUEUE
+}
+
+fn base_configuration() -> DatabaseConfiguration {
+    let mut configuration = DatabaseConfiguration {
+        connect_timeout_secs: 2,
+        ..Default::default()
+    };
+
+    if let Ok(url) = std::env::var("PGT_TEST_DATABASE_URL") {
+        if let Ok(parsed) = url::Url::parse(&url) {
+            configuration.host = parsed.host_str().unwrap_or("127.0.0.1").to_string();
+            configuration.port = parsed.port().unwrap_or(5432);
+            configuration.username = parsed.username().to_string();
+            configuration.password = parsed.password().unwrap_or_default().to_string();
+        }
+    }
+
+    configuration
+}
+
+fn random_suffix() -> String {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hash, Hasher};
+
+    let mut hasher = RandomState::new().build_hasher();
+    std::time::Instant::now().hash(&mut hasher);
+    std::process::id().hash(&mut hasher);
+    format!("{:x}", hasher.finish())
+}